@@ -2,7 +2,7 @@
 //!
 //! Recursively normalize directories and filenames to Unix friendly standard.
 //!
-//! No dependencies, really simple and fast.
+//! Simple and fast, with a single dependency ([clap](https://crates.io/crates/clap)) for argument parsing.
 //!
 //! ## Example
 //!
@@ -25,10 +25,16 @@
 //!
 //! FLAGS:
 //!     -a: Append the specified text at the end of the filename.
+//!         --ascii: Fold accented Latin-1 letters to their closest ASCII equivalent.
+//!         --backup[=CONTROL]: Back up an existing target before overwriting it (simple or numbered).
 //!     -h: Show this help information.
 //!     -i: Insert the specified text at the beginning of the filename.
 //!     -l: Transform the resulting filename into all lowercase characters.
-//!     -r: Remove these characters: '!"#$%&'()*+,/:;<=>?@[\]^`{|}~ªº'.
+//!     -n, --no-clobber: Do not overwrite an existing file; skip the rename instead.
+//!     -R: Recurse into directories, renaming children before their parent.
+//!     -r, --remove: Remove these characters: '!"#$%&'()*+,/:;<=>?@[\]^`{|}~ªº'.
+//!         --remove-chars <CHARS>: Use this custom character set instead of the default for -r/--remove.
+//!     -s, --separator <CHAR>: Character that spaces collapse into (default '_').
 //!     -t: Interactively asks for confirmation of each action.
 //!     -u: Transform the resulting filename into all uppercase characters.
 //!     -v: Show information about the performed actions.
@@ -47,40 +53,59 @@
 //!
 //! Use flag `-t` if you are insecure of the results.
 
-use std::env;
+use std::ffi::{OsStr, OsString};
 use std::fs;
 use std::io::{self, Write};
-use std::path::Path;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
 
-/// Help to use the binary.
-#[doc(hidden)]
-pub const USAGE: &str = "[FLAG]... DIRECTORY_OR_FILE...
-
-FLAGS:
-    -a: Append the specified text at the end of the filename.
-    -h: Show this help information.
-    -i: Insert the specified text at the beginning of the filename.
-    -l: Transform the resulting filename into all lowercase characters.
-    -r: Remove these characters: '!\"#$%&\'()*+,/:;<=>?@[\\]^`{|}~ªº'.
-    -t: Interactively asks for confirmation of each action.
-    -u: Transform the resulting filename into all uppercase characters.
-    -v: Show information about the performed actions";
+use clap::{App, Arg};
 
 /// Especial characters to be removed with option 'r'.
 const SPECIAL: &str = "!\"#$%&\'()*+,/:;<=>?@[\\]^`{|}~ªº"; // exclude ._-
 
-/// Valid characters used as parameter flags.
-const FLAGS: [char; 8] = ['a', 'h', 'i', 'l', 'r', 't', 'u', 'v'];
+/// Latin-1 Supplement letters mapped to their closest ASCII equivalent for
+/// option `--ascii`. A handful, like `ß`, expand to more than one character.
+const ASCII_MAP: &[(char, &str)] = &[
+    ('À', "A"), ('Á', "A"), ('Â', "A"), ('Ã', "A"), ('Ä', "A"), ('Å', "A"), ('Æ', "AE"),
+    ('Ç', "C"), ('È', "E"), ('É', "E"), ('Ê', "E"), ('Ë', "E"), ('Ì', "I"), ('Í', "I"),
+    ('Î', "I"), ('Ï', "I"), ('Ð', "D"), ('Ñ', "N"), ('Ò', "O"), ('Ó', "O"), ('Ô', "O"),
+    ('Õ', "O"), ('Ö', "O"), ('Ø', "O"), ('Ù', "U"), ('Ú', "U"), ('Û', "U"), ('Ü', "U"),
+    ('Ý', "Y"), ('Þ', "TH"), ('ß', "ss"),
+    ('à', "a"), ('á', "a"), ('â', "a"), ('ã', "a"), ('ä', "a"), ('å', "a"), ('æ', "ae"),
+    ('ç', "c"), ('è', "e"), ('é', "e"), ('ê', "e"), ('ë', "e"), ('ì', "i"), ('í', "i"),
+    ('î', "i"), ('ï', "i"), ('ð', "d"), ('ñ', "n"), ('ò', "o"), ('ó', "o"), ('ô', "o"),
+    ('õ', "o"), ('ö', "o"), ('ø', "o"), ('ù', "u"), ('ú', "u"), ('û', "u"), ('ü', "u"),
+    ('ý', "y"), ('þ', "th"), ('ÿ', "y"),
+];
+
+/// Combining diacritical marks (U+0300-U+036F) stripped by option `--ascii`.
+fn is_combining_mark(c: char) -> bool {
+    ('\u{0300}'..='\u{036F}').contains(&c)
+}
+
+/// Naming scheme used to back up a clobbered target (option `--backup`).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd)]
+#[doc(hidden)]
+pub enum Backup {
+    /// Append a single `~` to the existing target's name.
+    Simple,
+    /// Append `.~N~`, probing increasing `N` until a free name is found.
+    Numbered,
+}
 
 /// Structure to organize arguments.
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd)]
 #[doc(hidden)]
 pub struct Parsed {
-    pub app: String,      // append this to the file name (start)
-    pub ins: String,      // insert this to the file name (end)
-    pub me: String,       // 'name' of the executed binary
-    pub flg: Vec<char>,   // list of argument flags
+    pub app: String, // append this to the file name (end)
+    pub ins: String, // insert this to the file name (start)
+    pub me: String, // 'name' of the executed binary
+    pub flg: Vec<char>, // list of argument flags
     pub pos: Vec<String>, // list of positional arguments
+    pub backup: Option<Backup>, // how to back up a clobbered target, if at all
+    pub sep: String, // separator that spaces collapse into
+    pub remove: String, // character set removed by option 'r'
 }
 
 /// Implementation for the structure Args.
@@ -93,74 +118,357 @@ impl Parsed {
             me: String::new(),
             flg: Vec::new(),
             pos: Vec::new(),
+            backup: None,
+            sep: String::from("_"),
+            remove: String::from(SPECIAL),
         }
     }
 }
 
+/// Build the command-line interface definition.
+///
+/// Any short option that takes a value (`-a`, `-i`, `-s`) must be the last
+/// one in a combined short-flag group (e.g. `-lra TEXT`, not `-lar TEXT`),
+/// or its value come from the `--long=value` form — otherwise clap reads
+/// the rest of the group as that option's value.
+fn build_cli() -> App<'static> {
+    App::new("normie")
+        .version("0.1.0")
+        .about("Just another filename normalizer tool.")
+        .arg(
+            Arg::new("append")
+                .short('a')
+                .long("append")
+                .value_name("TEXT")
+                .takes_value(true)
+                .help("Append the specified text at the end of the filename."),
+        )
+        .arg(
+            Arg::new("insert")
+                .short('i')
+                .long("insert")
+                .value_name("TEXT")
+                .takes_value(true)
+                .help("Insert the specified text at the beginning of the filename."),
+        )
+        .arg(
+            Arg::new("lowercase")
+                .short('l')
+                .long("lowercase")
+                .conflicts_with("uppercase")
+                .help("Transform the resulting filename into all lowercase characters."),
+        )
+        .arg(
+            Arg::new("uppercase")
+                .short('u')
+                .long("uppercase")
+                .help("Transform the resulting filename into all uppercase characters."),
+        )
+        .arg(
+            // Deliberately a plain flag (no attached value): a short option
+            // that takes a value can't sit in the middle of a combined
+            // group like `-lra` without clap grabbing the rest of the
+            // group as that value. `--remove-chars` carries the custom set
+            // instead, so `-r` stays safe to combine with other flags.
+            Arg::new("remove")
+                .short('r')
+                .long("remove")
+                .help("Remove these characters: '!\"#$%&\'()*+,/:;<=>?@[\\]^`{|}~ªº'."),
+        )
+        .arg(
+            Arg::new("remove-chars")
+                .long("remove-chars")
+                .value_name("CHARS")
+                .takes_value(true)
+                .help("Use this custom character set instead of the default for -r/--remove."),
+        )
+        .arg(
+            Arg::new("separator")
+                .short('s')
+                .long("separator")
+                .value_name("CHAR")
+                .takes_value(true)
+                .validator(|v| {
+                    if v.chars().count() == 1 {
+                        Ok(())
+                    } else {
+                        Err(String::from("separator must be exactly one character"))
+                    }
+                })
+                .help("Character that spaces collapse into (default '_')."),
+        )
+        .arg(
+            Arg::new("ascii")
+                .long("ascii")
+                .help("Fold accented Latin-1 letters to their closest ASCII equivalent."),
+        )
+        .arg(
+            Arg::new("recursive")
+                .short('R')
+                .long("recursive")
+                .help("Recurse into directories, renaming children before their parent."),
+        )
+        .arg(
+            Arg::new("no-clobber")
+                .short('n')
+                .long("no-clobber")
+                .help("Do not overwrite an existing file; skip the rename instead."),
+        )
+        .arg(
+            Arg::new("backup")
+                .long("backup")
+                .value_name("CONTROL")
+                .takes_value(true)
+                .min_values(0)
+                .max_values(1)
+                .require_equals(true)
+                .default_missing_value("simple")
+                .possible_values(["simple", "numbered", "t"])
+                .conflicts_with("no-clobber")
+                .help("Back up an existing target before overwriting it (simple or numbered)."),
+        )
+        .arg(
+            Arg::new("interactive")
+                .short('t')
+                .long("interactive")
+                .help("Interactively asks for confirmation of each action."),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .help("Show information about the performed actions."),
+        )
+        .arg(
+            Arg::new("paths")
+                .value_name("DIRECTORY_OR_FILE")
+                .help("Directories or files to normalize.")
+                .multiple_values(true)
+                .required(true),
+        )
+}
+
 /// Organize and validate the arguments.
 #[doc(hidden)]
-pub fn arg_analyzer(mut args: env::Args) -> Result<Parsed, String> {
-    if args.len() <= 1 {
-        return Err(String::from("missing file operand"));
-    }
+pub fn arg_analyzer(args: impl Iterator<Item = String>) -> Parsed {
+    let args: Vec<String> = args.collect();
     let mut out = Parsed::new();
-    out.me = args.next().unwrap_or_default();
-    for arg in args {
-        if let Some(stripped) = arg.strip_prefix('-') {
-            out.flg.append(&mut stripped.chars().collect());
-        } else if (out.flg.contains(&'a') || out.flg.contains(&'i')) && !Path::new(&arg).exists() {
-            if out.flg.contains(&'a') && out.app.is_empty() {
-                out.app = arg;
-            } else if out.flg.contains(&'i') && out.ins.is_empty() {
-                out.ins = arg;
-            }
-        } else {
-            out.pos.push(arg);
+    out.me = args.first().cloned().unwrap_or_default();
+
+    let matches = build_cli().get_matches_from(args.iter().cloned());
+
+    if matches.is_present("append") {
+        out.flg.push('a');
+        out.app = matches.value_of("append").unwrap_or_default().to_string();
+    }
+    if matches.is_present("insert") {
+        out.flg.push('i');
+        out.ins = matches.value_of("insert").unwrap_or_default().to_string();
+    }
+    if matches.is_present("lowercase") {
+        out.flg.push('l');
+    }
+    if matches.is_present("uppercase") {
+        out.flg.push('u');
+    }
+    if matches.is_present("remove") {
+        out.flg.push('r');
+    }
+    if let Some(custom) = matches.value_of("remove-chars") {
+        out.remove = custom.to_string();
+        if !out.flg.contains(&'r') {
+            out.flg.push('r');
         }
     }
-    if out.flg.contains(&'h') {
-        return Ok(out);
+    if let Some(sep) = matches.value_of("separator") {
+        out.sep = sep.to_string();
+    }
+    if matches.is_present("ascii") {
+        out.flg.push('A');
+    }
+    if matches.is_present("recursive") {
+        out.flg.push('R');
+    }
+    if matches.is_present("no-clobber") {
+        out.flg.push('n');
+    }
+    if matches.is_present("backup") {
+        out.backup = match matches.value_of("backup") {
+            Some("numbered") | Some("t") => Some(Backup::Numbered),
+            _ => Some(Backup::Simple),
+        };
+    }
+    if matches.is_present("interactive") {
+        out.flg.push('t');
+    }
+    if matches.is_present("verbose") {
+        out.flg.push('v');
     }
-    if out.pos.is_empty() {
-        return Err(String::from("missing file operand"));
+    out.pos = matches
+        .values_of("paths")
+        .map(|v| v.map(String::from).collect())
+        .unwrap_or_default();
+
+    out
+}
+
+/// Replace every occurrence of `needle` in `bytes` with `replacement`.
+fn replace_bytes(bytes: &[u8], needle: &[u8], replacement: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(needle) {
+            out.extend_from_slice(replacement);
+            i += needle.len();
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Collapse consecutive repeats of `sep` into a single occurrence.
+fn collapse_runs(bytes: &[u8], sep: &[u8]) -> Vec<u8> {
+    if sep.is_empty() {
+        return bytes.to_vec();
     }
-    if out.flg.contains(&'l') && out.flg.contains(&'u') {
-        return Err(String::from("options 'l' and 'u' not allowed at same time"));
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(sep) {
+            out.extend_from_slice(sep);
+            i += sep.len();
+            while bytes[i..].starts_with(sep) {
+                i += sep.len();
+            }
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
     }
-    if out.flg.contains(&'a') && out.app.is_empty() {
-        return Err(String::from("missing text to append"));
+    out
+}
+
+/// Upper/lowercase every validly UTF-8-decodable run of `bytes`, leaving any
+/// invalid byte sequence untouched rather than dropping or replacing it.
+fn case_fold(bytes: &[u8], upper: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                let folded = if upper { valid.to_uppercase() } else { valid.to_lowercase() };
+                out.extend_from_slice(folded.as_bytes());
+                break;
+            }
+            Err(e) => {
+                let valid_up_to = e.valid_up_to();
+                let valid = std::str::from_utf8(&rest[..valid_up_to]).unwrap();
+                let folded = if upper { valid.to_uppercase() } else { valid.to_lowercase() };
+                out.extend_from_slice(folded.as_bytes());
+                match e.error_len() {
+                    Some(len) => {
+                        out.extend_from_slice(&rest[valid_up_to..valid_up_to + len]);
+                        rest = &rest[valid_up_to + len..];
+                    }
+                    None => {
+                        // Incomplete sequence trailing the buffer: keep it as-is.
+                        out.extend_from_slice(&rest[valid_up_to..]);
+                        break;
+                    }
+                }
+            }
+        }
     }
-    if out.flg.contains(&'i') && out.ins.is_empty() {
-        return Err(String::from("missing text to insert"));
+    out
+}
+
+/// Fold the accented Latin-1 letters listed in `ASCII_MAP` to their closest
+/// ASCII equivalent and drop stray combining diacritical marks, leaving any
+/// invalid byte sequence or unmapped character (e.g. CJK) untouched.
+fn ascii_fold(bytes: &[u8]) -> Vec<u8> {
+    fn fold_into(out: &mut Vec<u8>, valid: &str) {
+        for c in valid.chars() {
+            if is_combining_mark(c) {
+                continue;
+            }
+            match ASCII_MAP.iter().find(|(from, _)| *from == c) {
+                Some((_, to)) => out.extend_from_slice(to.as_bytes()),
+                None => {
+                    let mut buf = [0u8; 4];
+                    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+            }
+        }
     }
-    for c in &out.flg {
-        if !FLAGS.contains(c) {
-            return Err(format!("invalid option -- '{}'", c));
+
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                fold_into(&mut out, valid);
+                break;
+            }
+            Err(e) => {
+                let valid_up_to = e.valid_up_to();
+                let valid = std::str::from_utf8(&rest[..valid_up_to]).unwrap();
+                fold_into(&mut out, valid);
+                match e.error_len() {
+                    Some(len) => {
+                        out.extend_from_slice(&rest[valid_up_to..valid_up_to + len]);
+                        rest = &rest[valid_up_to + len..];
+                    }
+                    None => {
+                        // Incomplete sequence trailing the buffer: keep it as-is.
+                        out.extend_from_slice(&rest[valid_up_to..]);
+                        break;
+                    }
+                }
+            }
         }
     }
-    Ok(out)
+    out
 }
 
-/// Modify a string according to the options. Return a String.
-fn mod_str(text: &str, args: &Parsed) -> String {
-    let mut out = text.replace(|x| x == '\u{20}' || x == '\u{3000}', "_"); // common or ideographic
+/// Modify a filename according to the options. Return an OsString.
+///
+/// Works on the raw byte representation so that a name containing invalid
+/// UTF-8 is transformed where safe (ASCII removal/separator handling) and
+/// otherwise passed through untouched instead of being silently emptied.
+fn mod_str(text: &OsStr, args: &Parsed) -> OsString {
+    let sep = args.sep.as_bytes();
+    let mut bytes = text.as_bytes().to_vec();
+    bytes = replace_bytes(&bytes, b" ", sep); // common space
+    bytes = replace_bytes(&bytes, "\u{3000}".as_bytes(), sep); // ideographic space
     if args.flg.contains(&'a') && !args.app.is_empty() {
-        out.push_str(&args.app);
+        bytes.extend_from_slice(args.app.as_bytes());
     }
     if args.flg.contains(&'i') && !args.ins.is_empty() {
-        out.insert_str(0, &args.ins);
+        let mut prefixed = args.ins.as_bytes().to_vec();
+        prefixed.extend_from_slice(&bytes);
+        bytes = prefixed;
+    }
+    if args.flg.contains(&'A') {
+        bytes = ascii_fold(&bytes);
     }
     if args.flg.contains(&'r') {
-        for c in SPECIAL.chars() {
-            out = out.replace(c, "");
+        // Remove by exact byte-sequence match so a multi-byte custom
+        // character (e.g. an accented letter) can't clip an unrelated byte.
+        let mut buf = [0u8; 4];
+        for c in args.remove.chars() {
+            let enc = c.encode_utf8(&mut buf);
+            bytes = replace_bytes(&bytes, enc.as_bytes(), b"");
         }
     }
     if args.flg.contains(&'l') {
-        out = out.to_lowercase();
+        bytes = case_fold(&bytes, false);
     } else if args.flg.contains(&'u') {
-        out = out.to_uppercase();
+        bytes = case_fold(&bytes, true);
     }
-    out
+    bytes = collapse_runs(&bytes, sep);
+    OsString::from_vec(bytes)
 }
 
 /// Asks the user about renaming or not (option 't').
@@ -175,42 +483,93 @@ fn interactive(me: &str, old: &str, new: &str) -> Result<(), io::Error> {
     Ok(())
 }
 
+/// Find a name to move an existing target aside to, according to `mode`.
+fn backup_name(target: &Path, mode: &Backup) -> PathBuf {
+    match mode {
+        Backup::Simple => {
+            let mut name = target.as_os_str().to_os_string();
+            name.push("~");
+            PathBuf::from(name)
+        }
+        Backup::Numbered => {
+            let mut n = 1;
+            loop {
+                let mut name = target.as_os_str().to_os_string();
+                name.push(format!(".~{}~", n));
+                let candidate = PathBuf::from(name);
+                if !candidate.exists() {
+                    return candidate;
+                }
+                n += 1;
+            }
+        }
+    }
+}
+
 /// Rename a single directory/file 'p' using options 'args'.
-fn rename(p: &str, args: &Parsed) -> Result<(), String> {
-    if Path::new(p).exists() {
-        let path = Path::new(p);
-        let name = match path.file_name() {
-            Some(n) => n.to_str().unwrap_or_default(),
+fn rename(p: &Path, args: &Parsed) -> Result<(), String> {
+    if p.exists() {
+        let name = match p.file_name() {
+            Some(n) => n,
             None => {
                 return Err(format!(
                     "\x1b[1m{}\x1b[0m: {} has no valid name\n",
-                    args.me, p
+                    args.me,
+                    p.display()
                 ))
             }
         };
         let target = mod_str(name, args);
         if name == target {
-            return Err(format!(
-                "\x1b[1m{}\x1b[0m: nothing to do with '{}'\n",
-                args.me, p
-            ));
+            // Already normalized is a no-op, not a failure: a recursive run
+            // over a non-trivial tree will legitimately see plenty of these.
+            if args.flg.contains(&'v') {
+                println!("nothing to do with '{}'.", p.display());
+            }
+            return Ok(());
         }
-        let res = format!("{}{}", p.strip_suffix(name).unwrap_or_default(), target);
-        if args.flg.contains(&'t') && interactive(&args.me, p, &res).is_err() {
+        let res = p.with_file_name(&target);
+        if args.flg.contains(&'t')
+            && interactive(&args.me, &p.display().to_string(), &res.display().to_string())
+                .is_err()
+        {
             return Ok(());
         }
-        match fs::rename(&path, &res[..]) {
+        if res.exists() {
+            if args.flg.contains(&'n') {
+                if args.flg.contains(&'v') {
+                    println!("skipped '{}': '{}' already exists.", p.display(), res.display());
+                }
+                return Ok(());
+            }
+            if let Some(mode) = &args.backup {
+                let bak = backup_name(&res, mode);
+                if let Err(e) = fs::rename(&res, &bak) {
+                    return Err(format!(
+                        "\x1b[1m{}\x1b[0m: cannot back up '{}' to '{}': {}.\n",
+                        args.me,
+                        res.display(),
+                        bak.display(),
+                        e.to_string().split_once(" (").unwrap_or_default().0
+                    ));
+                }
+                if args.flg.contains(&'v') {
+                    println!("backed up '{}' as '{}'.", res.display(), bak.display());
+                }
+            }
+        }
+        match fs::rename(p, &res) {
             Ok(_) => {
                 if args.flg.contains(&'v') {
-                    println!("renamed '{}' to '{}'.", p, res);
+                    println!("renamed '{}' to '{}'.", p.display(), res.display());
                 }
             }
             Err(e) => {
                 return Err(format!(
                     "\x1b[1m{}\x1b[0m: cannot rename '{}' to '{}': {}.\n",
                     args.me,
-                    p,
-                    res,
+                    p.display(),
+                    res.display(),
                     e.to_string().split_once(" (").unwrap_or_default().0
                 ))
             }
@@ -218,17 +577,47 @@ fn rename(p: &str, args: &Parsed) -> Result<(), String> {
     } else {
         return Err(format!(
             "\x1b[1m{}\x1b[0m: '{}' is not a valid directory/file.\n",
-            args.me, p
+            args.me,
+            p.display()
         ));
     }
     Ok(())
 }
 
+/// Collect every entry under `dir`, depth-first, so that a directory's
+/// children always appear before the directory itself.
+fn walk(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("\x1b[1mnormie\x1b[0m: cannot read '{}': {}\n", dir.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if path.is_dir() {
+            walk(&path, out)?;
+        }
+        out.push(path);
+    }
+    Ok(())
+}
+
 /// Start the program according to parameters.
 #[doc(hidden)]
 pub fn run(args: Parsed) -> Result<(), String> {
     let mut e = String::new();
     for path in &args.pos {
+        let path = Path::new(path);
+        if args.flg.contains(&'R') && path.is_dir() {
+            let mut entries = Vec::new();
+            if let Err(errs) = walk(path, &mut entries) {
+                e.push_str(&errs);
+            }
+            entries.sort_by_key(|p| std::cmp::Reverse(p.components().count()));
+            for entry in &entries {
+                if let Err(errs) = rename(entry, &args) {
+                    e.push_str(&errs);
+                }
+            }
+        }
         if let Err(errs) = rename(path, &args) {
             e.push_str(&errs);
         }
@@ -249,25 +638,85 @@ mod tests {
 
     use super::*;
 
+    /// Drive the real clap parser, not a hand-built `Parsed`, so a
+    /// regression in combined short-flag handling (`-lra`) gets caught.
+    #[test]
+    fn test_arg_analyzer_combined_short_flags() {
+        let argv = ["normie", "-lra", ".tgz", "some file"];
+        let parsed = arg_analyzer(argv.iter().map(|s| s.to_string()));
+        assert_eq!(parsed.flg, vec!['a', 'l', 'r']);
+        assert_eq!(parsed.app, ".tgz");
+        assert_eq!(parsed.pos, vec!["some file"]);
+
+        let argv = ["normie", "-lrv", "some file"];
+        let parsed = arg_analyzer(argv.iter().map(|s| s.to_string()));
+        assert_eq!(parsed.flg, vec!['l', 'r', 'v']);
+        assert_eq!(parsed.remove, SPECIAL);
+        assert_eq!(parsed.pos, vec!["some file"]);
+    }
+
+    /// `--backup` has an optional value, so without `require_equals` clap
+    /// would eagerly swallow the next bare positional as CONTROL.
+    #[test]
+    fn test_arg_analyzer_backup_does_not_swallow_positionals() {
+        let argv = ["normie", "--backup", "A!", "A?"];
+        let parsed = arg_analyzer(argv.iter().map(|s| s.to_string()));
+        assert_eq!(parsed.backup, Some(Backup::Simple));
+        assert_eq!(parsed.pos, vec!["A!", "A?"]);
+
+        let argv = ["normie", "--backup", "simple", "A?"];
+        let parsed = arg_analyzer(argv.iter().map(|s| s.to_string()));
+        assert_eq!(parsed.backup, Some(Backup::Simple));
+        assert_eq!(parsed.pos, vec!["simple", "A?"]);
+
+        let argv = ["normie", "--backup=numbered", "A?"];
+        let parsed = arg_analyzer(argv.iter().map(|s| s.to_string()));
+        assert_eq!(parsed.backup, Some(Backup::Numbered));
+        assert_eq!(parsed.pos, vec!["A?"]);
+    }
+
     #[test]
     fn test_mod_str() {
         let mut args = Parsed::new();
         args.flg = vec!['u'];
-        assert_eq!(mod_str("upper-case", &args), "UPPER-CASE");
+        assert_eq!(mod_str(OsStr::new("upper-case"), &args), "UPPER-CASE");
         args.flg = vec!['u', 'a'];
         args.app = String::from("-case");
-        assert_eq!(mod_str("upper", &args), "UPPER-CASE");
+        assert_eq!(mod_str(OsStr::new("upper"), &args), "UPPER-CASE");
         args.flg = vec!['u', 'i', 'r'];
         args.app = String::new();
         args.ins = String::from("upper");
-        assert_eq!(mod_str("-case", &args), "UPPER-CASE");
+        assert_eq!(mod_str(OsStr::new("-case"), &args), "UPPER-CASE");
         args.flg = vec!['l'];
-        assert_eq!(mod_str("Ho lA.LaY", &args), "ho_la.lay");
+        assert_eq!(mod_str(OsStr::new("Ho lA.LaY"), &args), "ho_la.lay");
         args.flg = vec!['r', 'u'];
-        assert_eq!(mod_str("Ho lA.LaY", &args), "HO_LA.LAY");
-        assert_eq!(mod_str("u'e&pª\".lay", &args), "UEP.LAY");
-        assert_eq!(mod_str("バンドメイド", &args), "バンドメイド");
-        assert_eq!(mod_str("ぽ\u{3000}", &args), "ぽ_");
+        assert_eq!(mod_str(OsStr::new("Ho lA.LaY"), &args), "HO_LA.LAY");
+        assert_eq!(mod_str(OsStr::new("u'e&pª\".lay"), &args), "UEP.LAY");
+        assert_eq!(mod_str(OsStr::new("バンドメイド"), &args), "バンドメイド");
+        assert_eq!(mod_str(OsStr::new("ぽ\u{3000}"), &args), "ぽ_");
+        args.flg = vec![];
+        args.sep = String::from("-");
+        assert_eq!(mod_str(OsStr::new("a  b   c"), &args), "a-b-c");
+        args.flg = vec!['r'];
+        args.remove = String::from("ªº");
+        assert_eq!(mod_str(OsStr::new("u'e&pª\".lay"), &args), "u'e&p\".lay");
+        args.flg = vec!['A'];
+        args.remove = String::from(SPECIAL);
+        assert_eq!(mod_str(OsStr::new("café"), &args), "cafe");
+        assert_eq!(mod_str(OsStr::new("Straße"), &args), "Strasse");
+        assert_eq!(mod_str(OsStr::new("Ñandú"), &args), "Nandu");
+        assert_eq!(mod_str(OsStr::new("e\u{0301}clair"), &args), "eclair");
+        assert_eq!(mod_str(OsStr::new("バンドメイド"), &args), "バンドメイド");
+    }
+
+    #[test]
+    fn test_mod_str_invalid_utf8() {
+        let mut args = Parsed::new();
+        args.flg = vec!['u'];
+        // 0xFF is not valid UTF-8 on its own; it must survive untouched.
+        let name = OsString::from_vec(vec![b'a', 0xFF, b'b']);
+        let got = mod_str(&name, &args);
+        assert_eq!(got.as_bytes(), &[b'A', 0xFF, b'B']);
     }
 
     #[test]
@@ -277,31 +726,116 @@ mod tests {
         fs::File::create(p).unwrap();
         args.flg = vec!['i', 'l', 'r'];
         args.ins = String::from("THIS-");
-        assert!(rename(p, &args).is_ok());
+        assert!(rename(Path::new(p), &args).is_ok());
         fs::remove_file("/tmp/this-sucks").unwrap();
 
         let p = "/tmp/=>IS<=";
         let mut args = Parsed::new();
-        fs::File::create(&p).unwrap();
+        fs::File::create(p).unwrap();
         args.flg = vec!['a', 'l', 'r'];
         args.app = String::from("-GOOD");
-        assert!(rename(&p, &args).is_ok());
+        assert!(rename(Path::new(p), &args).is_ok());
         fs::remove_file("/tmp/is-good").unwrap();
 
         let p = "/tmp/B)E(T%T@E*R T*H*I&S W@A*Y#";
         let mut args = Parsed::new();
-        fs::File::create(&p).unwrap();
+        fs::File::create(p).unwrap();
         args.flg = vec!['l', 'r', 'a'];
         args.app = String::from(".tgz");
-        assert!(rename(&p, &args).is_ok());
+        assert!(rename(Path::new(p), &args).is_ok());
         fs::remove_file("/tmp/better_this_way.tgz").unwrap();
 
         let p = "/tmp/G)O(O%@D N*A*M&E@**#";
         let mut args = Parsed::new();
-        fs::File::create(&p).unwrap();
+        fs::File::create(p).unwrap();
         args.flg = vec!['l', 'r', 'a'];
         args.app = String::from(".tgz");
-        assert!(rename(&p, &args).is_ok());
+        assert!(rename(Path::new(p), &args).is_ok());
         fs::remove_file("/tmp/good_name.tgz").unwrap();
     }
+
+    #[test]
+    fn test_no_clobber_and_backup() {
+        let src = "/tmp/=>CLASH<=";
+        let dst = "/tmp/clash";
+        fs::File::create(src).unwrap();
+        fs::File::create(dst).unwrap();
+
+        let mut args = Parsed::new();
+        args.flg = vec!['l', 'r', 'n'];
+        assert!(rename(Path::new(src), &args).is_ok());
+        assert!(Path::new(src).exists());
+        assert!(Path::new(dst).exists());
+
+        let mut args = Parsed::new();
+        args.flg = vec!['l', 'r'];
+        args.backup = Some(Backup::Simple);
+        assert!(rename(Path::new(src), &args).is_ok());
+        assert!(!Path::new(src).exists());
+        assert!(Path::new(dst).exists());
+        assert!(Path::new("/tmp/clash~").exists());
+
+        fs::remove_file(dst).unwrap();
+        fs::remove_file("/tmp/clash~").unwrap();
+    }
+
+    #[test]
+    fn test_numbered_backup_probes_for_free_slot() {
+        let src = "/tmp/=>NUMBERED CLASH<=";
+        let dst = "/tmp/numbered_clash";
+        fs::File::create(src).unwrap();
+        fs::File::create(dst).unwrap();
+        // Occupy the first numbered slot so the probing logic has to skip it.
+        fs::File::create(format!("{}.~1~", dst)).unwrap();
+
+        let mut args = Parsed::new();
+        args.flg = vec!['l', 'r'];
+        args.backup = Some(Backup::Numbered);
+        assert!(rename(Path::new(src), &args).is_ok());
+        assert!(!Path::new(src).exists());
+        assert!(Path::new(dst).exists());
+        assert!(Path::new(&format!("{}.~1~", dst)).exists());
+        assert!(Path::new(&format!("{}.~2~", dst)).exists());
+
+        fs::remove_file(dst).unwrap();
+        fs::remove_file(format!("{}.~1~", dst)).unwrap();
+        fs::remove_file(format!("{}.~2~", dst)).unwrap();
+    }
+
+    #[test]
+    fn test_recursive_rename() {
+        let root = "/tmp/PARENT DIR";
+        let child = "/tmp/PARENT DIR/CHILD DIR";
+        fs::create_dir_all(child).unwrap();
+        fs::File::create(format!("{}/LEAF FILE", child)).unwrap();
+
+        let mut args = Parsed::new();
+        args.me = String::from("normie");
+        args.flg = vec!['l', 'R'];
+        args.pos = vec![String::from(root)];
+        assert!(run(args).is_ok());
+
+        assert!(Path::new("/tmp/parent_dir/child_dir/leaf_file").exists());
+        fs::remove_dir_all("/tmp/parent_dir").unwrap();
+    }
+
+    #[test]
+    fn test_recursive_rename_ignores_already_normalized_entries() {
+        let root = "/tmp/already_normal";
+        let child = "/tmp/already_normal/already_normal_child";
+        fs::create_dir_all(child).unwrap();
+        fs::File::create(format!("{}/LEAF FILE", child)).unwrap();
+        fs::File::create(format!("{}/already_normal_leaf", child)).unwrap();
+
+        let mut args = Parsed::new();
+        args.me = String::from("normie");
+        args.flg = vec!['l', 'R'];
+        args.pos = vec![String::from(root)];
+        assert!(run(args).is_ok());
+
+        assert!(Path::new("/tmp/already_normal/already_normal_child/leaf_file").exists());
+        assert!(Path::new("/tmp/already_normal/already_normal_child/already_normal_leaf")
+            .exists());
+        fs::remove_dir_all("/tmp/already_normal").unwrap();
+    }
 }